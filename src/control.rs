@@ -1,95 +1,272 @@
-use crate::models::PeepFrame;
+use crate::models::{AppMode, PeepFrame, SqlInputState};
 use crate::render;
-use crossterm::event::{read, Event, KeyCode};
-use crossterm::terminal::{self, ClearType};
+use crossterm::event::{Event, EventStream, KeyCode, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
 use crossterm::{
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
 };
+use futures::StreamExt;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use polars::error::PolarsError;
+use ratatui::backend::CrosstermBackend;
+use ratatui::Terminal;
 
 use std::error::Error;
-use std::io::stdout;
-use std::io::{self};
+use std::io::{stdout, Stdout};
+use std::path::Path;
 
-pub fn event_loop(
+/// Lines reserved outside the table area: the header, the view-data bar and
+/// the controls guide (1 each), plus the table widget's own chrome - its top
+/// and bottom borders and its column-header row - so a resize never clips
+/// rows the `Table` widget doesn't actually have room to draw.
+const RESERVED_LINES: u16 = 3 + TABLE_CHROME_LINES;
+
+/// Lines the `Table` widget itself consumes inside its chunk: one header row
+/// plus a bordered block's top and bottom border lines.
+const TABLE_CHROME_LINES: u16 = 3;
+
+pub async fn event_loop(
     filepath: &str,
     display_rows: usize,
     display_cols: usize,
+    row_override: Option<usize>,
+    col_override: Option<usize>,
 ) -> Result<(), Box<dyn Error>> {
     let mut peep_frame = PeepFrame::from_file(filepath, display_rows, display_cols)?;
 
-    // initial render
+    // the terminal may already differ from the CLI-provided row/col counts
+    // by the time we draw the first frame, so size against it up front -
+    // unless the user passed -r/-c explicitly, which pins the window
+    if let Ok((width, height)) = crossterm::terminal::size() {
+        let (display_rows, display_cols) =
+            compute_display_dims(width, height, row_override, col_override);
+        peep_frame.resize(display_rows, display_cols);
+    }
+
+    enable_raw_mode()?;
     execute!(stdout(), EnterAlternateScreen)?;
-    render::render(&peep_frame).expect("Unable to render");
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
 
-    loop {
-        // Blocking read
-        let event = read()?;
+    let result = run(&mut terminal, peep_frame, row_override, col_override).await;
 
-        if event == Event::Key(KeyCode::Down.into()) {
-            peep_frame.down();
-        }
-        if event == Event::Key(KeyCode::Up.into()) {
-            peep_frame.up();
-        }
-        if event == Event::Key(KeyCode::Left.into()) {
-            peep_frame.left();
-        }
-        if event == Event::Key(KeyCode::Right.into()) {
-            peep_frame.right();
-        }
-        if event == Event::Key(KeyCode::Char('t').into()) {
-            peep_frame.jump_to_tail();
-        }
-        if event == Event::Key(KeyCode::Char('h').into()) {
-            peep_frame.jump_to_head();
-        }
-        if event == Event::Key(KeyCode::Char('r').into()) {
-            peep_frame.shuffle_rows()?;
-        }
-        if event == Event::Key(KeyCode::Char('s').into()) {
-            println!("SQL (enter 'q' to exit this mode):");
-            loop {
-                let mut input = String::new();
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
 
-                io::stdin().read_line(&mut input).unwrap();
+    result
+}
 
-                let sql_query = input.trim();
+async fn run(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    mut peep_frame: PeepFrame,
+    row_override: Option<usize>,
+    col_override: Option<usize>,
+) -> Result<(), Box<dyn Error>> {
+    let mut events = EventStream::new();
 
-                if sql_query == 'q'.to_string() {
-                    break;
-                }
+    // fed by the `notify` watcher while following is on; stays idle otherwise
+    let (fs_tx, mut fs_rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher: Option<RecommendedWatcher> = None;
+
+    terminal.draw(|frame| render::render(frame, &peep_frame))?;
+
+    loop {
+        tokio::select! {
+            maybe_event = events.next() => {
+                let event = match maybe_event {
+                    Some(Ok(event)) => event,
+                    Some(Err(err)) => return Err(err.into()),
+                    None => break,
+                };
 
-                match peep_frame.execute_sql(sql_query) {
-                    Ok(_) => break,
-                    Err(err) => {
-                        // for polars errors, use a more concise print
-                        match err.is::<PolarsError>() {
-                            true => {println!("{}", err)}
-                            false => {println!("{:?}", err);}
+                match peep_frame.mode.clone() {
+                    AppMode::Normal => {
+                        if let Event::Resize(width, height) = event {
+                            let (display_rows, display_cols) =
+                                compute_display_dims(width, height, row_override, col_override);
+                            peep_frame.resize(display_rows, display_cols);
+                        }
+                        if event == Event::Key(KeyCode::Down.into()) {
+                            peep_frame.down();
+                        }
+                        if event == Event::Key(KeyCode::Up.into()) {
+                            peep_frame.up();
+                        }
+                        if event == Event::Key(KeyCode::Left.into()) {
+                            peep_frame.left();
+                        }
+                        if event == Event::Key(KeyCode::Right.into()) {
+                            peep_frame.right();
+                        }
+                        if event == Event::Key(KeyCode::Char('t').into()) {
+                            peep_frame.jump_to_tail();
+                        }
+                        if event == Event::Key(KeyCode::Char('h').into()) {
+                            peep_frame.jump_to_head();
+                        }
+                        if event == Event::Key(KeyCode::Char('r').into()) {
+                            peep_frame.shuffle_rows()?;
+                        }
+                        if event == Event::Key(KeyCode::Char('s').into()) {
+                            peep_frame.mode = AppMode::SqlInput(SqlInputState::default());
+                        }
+                        if event == Event::Key(KeyCode::Char('o').into()) {
+                            peep_frame.reset_to_original()?;
+                        }
+                        if event == Event::Key(KeyCode::Char('f').into()) {
+                            peep_frame.toggle_following();
+                            watcher = if peep_frame.following {
+                                Some(watch_file(&peep_frame.filepath, fs_tx.clone())?)
+                            } else {
+                                None
+                            };
+                        }
+                        if event == Event::Key(KeyCode::Char('q').into()) {
+                            break;
+                        }
+                    }
+                    AppMode::SqlInput(_) => {
+                        if let Event::Resize(width, height) = event {
+                            let (display_rows, display_cols) =
+                                compute_display_dims(width, height, row_override, col_override);
+                            peep_frame.resize(display_rows, display_cols);
+                        }
+                        if let Event::Key(key_event) = event {
+                            match key_event.code {
+                                KeyCode::Esc => {
+                                    peep_frame.mode = AppMode::Normal;
+                                }
+                                // Ctrl+X submits. Ctrl+Enter is not distinguishable from
+                                // plain Enter on the wire (both send a bare CR) without the
+                                // Kitty keyboard protocol, so Enter always inserts a newline
+                                // and submission needs its own control character instead.
+                                KeyCode::Char('x') if key_event.modifiers.contains(KeyModifiers::CONTROL) => {
+                                    if let AppMode::SqlInput(state) = &peep_frame.mode {
+                                        let query = state.buffer.clone();
+
+                                        match peep_frame.execute_sql(&query) {
+                                            Ok(_) => peep_frame.mode = AppMode::Normal,
+                                            Err(err) => {
+                                                // for polars errors, use a more concise print
+                                                let message = match err.is::<PolarsError>() {
+                                                    true => format!("{}", err),
+                                                    false => format!("{:?}", err),
+                                                };
+
+                                                if let AppMode::SqlInput(state) = &mut peep_frame.mode {
+                                                    state.error = Some(message);
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                                // plain Enter always inserts a newline; see the Ctrl+X arm above
+                                KeyCode::Enter => {
+                                    if let AppMode::SqlInput(state) = &mut peep_frame.mode {
+                                        state.insert_char('\n');
+                                    }
+                                }
+                                KeyCode::Backspace => {
+                                    if let AppMode::SqlInput(state) = &mut peep_frame.mode {
+                                        state.backspace();
+                                    }
+                                }
+                                KeyCode::Left => {
+                                    if let AppMode::SqlInput(state) = &mut peep_frame.mode {
+                                        state.move_left();
+                                    }
+                                }
+                                KeyCode::Right => {
+                                    if let AppMode::SqlInput(state) = &mut peep_frame.mode {
+                                        state.move_right();
+                                    }
+                                }
+                                KeyCode::Up => {
+                                    let history = peep_frame.sql_history.clone();
+                                    if let AppMode::SqlInput(state) = &mut peep_frame.mode {
+                                        state.history_up(&history);
+                                    }
+                                }
+                                KeyCode::Down => {
+                                    let history = peep_frame.sql_history.clone();
+                                    if let AppMode::SqlInput(state) = &mut peep_frame.mode {
+                                        state.history_down(&history);
+                                    }
+                                }
+                                KeyCode::Char(c) => {
+                                    if let AppMode::SqlInput(state) = &mut peep_frame.mode {
+                                        state.insert_char(c);
+                                    }
+                                }
+                                _ => {}
+                            }
                         }
                     }
                 }
-            }
-        }
-        if event == Event::Key(KeyCode::Char('o').into()) {
-            peep_frame.reset_to_original()?;
-        }
-        if event == Event::Key(KeyCode::Char('q').into()) {
-            execute!(stdout(), LeaveAlternateScreen)?;
-            break;
+            },
+            Some(fs_event) = fs_rx.recv() => {
+                // the watch is on the parent directory, so filter down to
+                // events that actually touch our file
+                if let Ok(event) = fs_event {
+                    let target_name = Path::new(&peep_frame.filepath).file_name();
+                    if event.paths.iter().any(|p| p.file_name() == target_name) {
+                        peep_frame.reload_from_disk()?;
+                    }
+                }
+            },
         }
 
-        // re-render
-        clear_screen()?;
-        render::render(&peep_frame)?;
+        terminal.draw(|frame| render::render(frame, &peep_frame))?;
     }
 
     Ok(())
 }
 
-fn clear_screen() -> Result<(), Box<dyn Error>> {
-    let res = execute!(stdout(), terminal::Clear(ClearType::All))?;
-    Ok(res)
+/// Derives a display row/column window from the live terminal size, reserving
+/// `RESERVED_LINES` for the surrounding UI and the table widget's own chrome.
+/// `row_override`/`col_override` come from an explicit `-r`/`-c` CLI flag and
+/// pin that dimension instead of deriving it from the terminal size.
+fn compute_display_dims(
+    width: u16,
+    height: u16,
+    row_override: Option<usize>,
+    col_override: Option<usize>,
+) -> (usize, usize) {
+    let display_rows =
+        row_override.unwrap_or_else(|| height.saturating_sub(RESERVED_LINES).max(1) as usize);
+
+    const AVG_COL_WIDTH: u16 = 12;
+    let display_cols = col_override.unwrap_or_else(|| (width / AVG_COL_WIDTH).max(1) as usize);
+
+    (display_rows, display_cols)
+}
+
+/// Starts watching `filepath` for modify/rename events, forwarding them to
+/// `tx` so the event loop can pick them up alongside keyboard input.
+///
+/// Watches the parent directory rather than the file itself: upstream jobs
+/// commonly replace a file via write-tmp-then-rename, which swaps out the
+/// inode a direct file watch is bound to, silently killing the watch. The
+/// caller filters the directory's events down to the target file name.
+fn watch_file(
+    filepath: &str,
+    tx: tokio::sync::mpsc::UnboundedSender<notify::Result<notify::Event>>,
+) -> Result<RecommendedWatcher, Box<dyn Error>> {
+    let path = Path::new(filepath);
+    let parent = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        // renames arrive as `Modify(Name(_))`, so this keeps both kinds
+        if let Ok(event) = &res {
+            if !matches!(event.kind, notify::EventKind::Modify(_)) {
+                return;
+            }
+        }
+        let _ = tx.send(res);
+    })?;
+    watcher.watch(parent, RecursiveMode::NonRecursive)?;
+    Ok(watcher)
 }
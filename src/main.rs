@@ -3,21 +3,22 @@ mod file_utils;
 mod models;
 mod render;
 
-use clap::{arg, command, value_parser};
+use clap::{arg, command, parser::ValueSource, value_parser};
 use control::event_loop;
 use std::env;
 
-fn main() {
+#[tokio::main]
+async fn main() {
     let matches = command!()
         .arg(arg!([filepath] "Required filepath to operate on").required(true))
         .arg(
-            arg!([row_count])
+            arg!([row_count] "Row window to display; overrides the terminal-derived size if passed explicitly")
                 .short('r')
                 .value_parser(value_parser!(usize))
                 .default_value("7"),
         )
         .arg(
-            arg!([col_count])
+            arg!([col_count] "Column window to display; overrides the terminal-derived size if passed explicitly")
                 .short('c')
                 .value_parser(value_parser!(usize))
                 .default_value("5"),
@@ -32,9 +33,24 @@ fn main() {
 
     let display_cols = matches.get_one::<usize>("col_count").unwrap().to_owned();
 
+    // only treat -r/-c as a hard override when the user actually passed them;
+    // otherwise the terminal-derived auto-size from the resize rework applies
+    let row_override = (matches.value_source("row_count") == Some(ValueSource::CommandLine))
+        .then_some(display_rows);
+    let col_override = (matches.value_source("col_count") == Some(ValueSource::CommandLine))
+        .then_some(display_cols);
+
     configure_polars_formatting();
 
-    event_loop(&filepath, display_rows, display_cols).expect("Cannot handle event");
+    event_loop(
+        &filepath,
+        display_rows,
+        display_cols,
+        row_override,
+        col_override,
+    )
+    .await
+    .expect("Cannot handle event");
 }
 
 fn configure_polars_formatting() {
@@ -11,10 +11,136 @@ pub enum ModifierState {
     RandomRows,
 }
 
+/// The foreground UI mode: normal table browsing, or the in-frame SQL modal
+#[derive(Clone)]
+pub enum AppMode {
+    Normal,
+    SqlInput(SqlInputState),
+}
+
+/// Editable buffer backing the `s` SQL modal: text, cursor position, the
+/// error from the last failed query (shown in the modal rather than printed),
+/// and the in-progress history walk triggered by Up/Down.
+#[derive(Clone, Default)]
+pub struct SqlInputState {
+    pub buffer: String,
+    pub cursor: usize,
+    pub error: Option<String>,
+    history_cursor: Option<usize>,
+    draft: Option<String>,
+}
+
+impl SqlInputState {
+    pub fn insert_char(&mut self, c: char) {
+        let byte_idx = self.byte_index();
+        self.buffer.insert(byte_idx, c);
+        self.cursor += 1;
+        self.error = None;
+    }
+
+    pub fn backspace(&mut self) {
+        if self.cursor == 0 {
+            return;
+        }
+
+        let byte_idx = self.byte_index();
+        let prev_byte_idx = self.buffer[..byte_idx]
+            .char_indices()
+            .next_back()
+            .map(|(i, _)| i)
+            .unwrap_or(0);
+
+        self.buffer.drain(prev_byte_idx..byte_idx);
+        self.cursor -= 1;
+        self.error = None;
+    }
+
+    pub fn move_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn move_right(&mut self) {
+        self.cursor = (self.cursor + 1).min(self.buffer.chars().count());
+    }
+
+    /// Position of the cursor expressed as (line, column), for rendering a
+    /// cursor inside a multi-line buffer.
+    pub fn cursor_position(&self) -> (usize, usize) {
+        let mut line = 0;
+        let mut col = 0;
+
+        for (i, c) in self.buffer.chars().enumerate() {
+            if i == self.cursor {
+                break;
+            }
+            if c == '\n' {
+                line += 1;
+                col = 0;
+            } else {
+                col += 1;
+            }
+        }
+
+        (line, col)
+    }
+
+    /// Walks one entry further back in `history`, stashing the in-progress
+    /// buffer on the first step so Down can return to it.
+    pub fn history_up(&mut self, history: &[String]) {
+        if history.is_empty() {
+            return;
+        }
+
+        let next_index = match self.history_cursor {
+            None => {
+                self.draft = Some(self.buffer.clone());
+                history.len() - 1
+            }
+            Some(0) => 0,
+            Some(i) => i - 1,
+        };
+
+        self.history_cursor = Some(next_index);
+        self.set_buffer(history[next_index].clone());
+    }
+
+    /// Walks one entry forward in `history`, returning to the stashed draft
+    /// once the walk runs past the most recent entry.
+    pub fn history_down(&mut self, history: &[String]) {
+        match self.history_cursor {
+            None => {}
+            Some(i) if i + 1 < history.len() => {
+                self.history_cursor = Some(i + 1);
+                self.set_buffer(history[i + 1].clone());
+            }
+            Some(_) => {
+                self.history_cursor = None;
+                self.set_buffer(self.draft.take().unwrap_or_default());
+            }
+        }
+    }
+
+    fn set_buffer(&mut self, text: String) {
+        self.cursor = text.chars().count();
+        self.buffer = text;
+        self.error = None;
+    }
+
+    fn byte_index(&self) -> usize {
+        self.buffer
+            .char_indices()
+            .nth(self.cursor)
+            .map(|(i, _)| i)
+            .unwrap_or(self.buffer.len())
+    }
+}
+
 /// LazyFrame combining table metadata and a stateful view window
 #[derive(Clone)]
 pub struct PeepFrame {
     pub file_name: String,
+    pub filepath: String,
+    file_type: file_utils::FileType,
     pub current_lazy_frame: LazyFrame,
     pub original_lazy_frame: LazyFrame,
     pub max_rows: usize,
@@ -25,6 +151,14 @@ pub struct PeepFrame {
     pub modifier_state: ModifierState,
     pub display_rows: usize,
     display_cols: usize,
+    /// Whether the view is following the file on disk (`f` to toggle)
+    pub following: bool,
+    pub mode: AppMode,
+    /// The SQL text behind the current `Queried` view, shown in the header
+    pub active_query: Option<String>,
+    /// Previously executed queries, most recent last; persisted to
+    /// `~/.peepers_history`
+    pub sql_history: Vec<String>,
 }
 impl PeepFrame {
     /// Constructs a `PeepFrame` from a file.
@@ -40,24 +174,9 @@ impl PeepFrame {
             .ok_or("File should have a name")?
             .to_string();
 
-        let lf = match file_utils::extract_file_type(file_path)? {
-            file_utils::FileType::Parquet => {
-                LazyFrame::scan_parquet(file_path, ScanArgsParquet::default())?
-            }
-            file_utils::FileType::Csv => LazyCsvReader::new(file_path).finish()?,
-        };
+        let file_type = file_utils::extract_file_type(file_path)?;
 
-        let max_rows = lf
-            .clone()
-            .select([count().alias("count")])
-            .collect()
-            .unwrap()
-            .column("count")
-            .unwrap()
-            .u32()
-            .unwrap()
-            .get(0)
-            .unwrap() as usize;
+        let (lf, max_rows) = scan_file(file_path, &file_type)?;
 
         let schema = lf.clone().schema()?;
 
@@ -67,6 +186,8 @@ impl PeepFrame {
 
         Ok(PeepFrame {
             file_name,
+            filepath: file_path.to_string(),
+            file_type,
             current_lazy_frame: lf.clone(),
             original_lazy_frame: lf,
             max_rows,
@@ -77,18 +198,61 @@ impl PeepFrame {
             modifier_state: ModifierState::Original,
             display_rows,
             display_cols,
+            following: false,
+            mode: AppMode::Normal,
+            active_query: None,
+            sql_history: load_history(),
         })
     }
 
+    /// Re-reads `filepath` from disk, refreshing the schema and row/col
+    /// counts, for use after a `notify` watcher reports the file changed.
+    /// If the view is following the tail, it jumps back to the tail so the
+    /// newly appended rows are visible.
+    pub fn reload_from_disk(&mut self) -> Result<(), Box<dyn Error>> {
+        let was_at_tail = self.row_slice_state.1 >= self.max_rows;
+
+        let (lf, max_rows) = scan_file(&self.filepath, &self.file_type)?;
+
+        let schema = lf.clone().schema()?;
+
+        let col_names: Vec<String> = schema.get_names().iter().map(|s| s.to_string()).collect();
+
+        let max_cols = col_names.len();
+
+        self.original_lazy_frame = lf.clone();
+        self.current_lazy_frame = lf;
+        self.max_rows = max_rows;
+        self.max_cols = max_cols;
+        self.col_names = col_names;
+        self.modifier_state = ModifierState::Original;
+
+        self.row_slice_state = (
+            self.row_slice_state.0.min(max_rows),
+            (self.row_slice_state.0 + self.display_rows).min(max_rows),
+        );
+        self.col_slice_state = (0, self.display_cols.min(max_cols));
+
+        if self.following && was_at_tail {
+            self.jump_to_tail();
+        }
+
+        Ok(())
+    }
+
     /// Updates the `PeepFrame` with a new `LazyFrame`.
     /// This function takes a `LazyFrame` as an argument, calculates the total number of rows and columns,
     /// extracts the column names, and updates the `PeepFrame`'s fields accordingly.
     /// This function will return an error if the `LazyFrame`'s schema cannot be retrieved.
     /// Takes modifier state as param, forcing both lf and state to be changed together
     fn update_with(&mut self, lf: &LazyFrame, new_modifier_state: ModifierState) -> Result<(), Box<dyn Error>> {
+        // `lf` here may be a queried/sampled frame rather than a raw file
+        // scan, so there is no footer to read the row count from - stream
+        // the count instead of materializing the whole thing.
         let max_rows = lf
             .clone()
             .select([count().alias("count")])
+            .with_streaming(true)
             .collect()?
             .column("count")?
             .u32()?
@@ -174,6 +338,21 @@ impl PeepFrame {
         self.row_slice_state = (0, self.display_rows.min(self.max_rows));
     }
 
+    /// Recomputes the display window in response to a terminal resize,
+    /// re-clamping the current row/col slices against the new bounds.
+    pub fn resize(&mut self, display_rows: usize, display_cols: usize) {
+        self.display_rows = display_rows;
+        self.display_cols = display_cols;
+
+        let (a, _) = self.row_slice_state;
+        let a = a.min(self.max_rows);
+        self.row_slice_state = (a, (a + display_rows).min(self.max_rows));
+
+        let (a, _) = self.col_slice_state;
+        let a = a.min(self.max_cols);
+        self.col_slice_state = (a, (a + display_cols).min(self.max_cols));
+    }
+
     // each execution acts on the original lf, and updates the current lf
     // this prevents the current lf from having an increasingly complex plan
     // and higher likelihood of stack overflow
@@ -185,9 +364,29 @@ impl PeepFrame {
 
         self.update_with(&new_lazy_frame, ModifierState::Queried)?;
 
+        self.active_query = Some(sql_query.to_string());
+        self.remember_query(sql_query);
+
         Ok(())
     }
 
+    /// Appends `sql_query` to the in-memory ring buffer (deduping immediate
+    /// repeats) and persists it to the history dotfile.
+    fn remember_query(&mut self, sql_query: &str) {
+        let flattened = flatten_query(sql_query);
+
+        if self.sql_history.last().map(String::as_str) == Some(flattened.as_str()) {
+            return;
+        }
+
+        self.sql_history.push(flattened.clone());
+        if self.sql_history.len() > MAX_HISTORY {
+            self.sql_history.remove(0);
+        }
+
+        append_history(&flattened);
+    }
+
     // each execution acts on the original lf, and updates the current lf
     // same considerations as execute_sql
     // but this one is not lazy? map materializes the whole frame (alternative is some complex row-wise sampling)
@@ -197,7 +396,10 @@ impl PeepFrame {
             .first()
             .ok_or("PeepFrame should have column names")?;
 
-        let sample_size = self.display_rows.clone();
+        // display_rows now tracks the live terminal height (see `resize`), so
+        // it can exceed the file's own row count for small files - clamp it
+        // or `sample_col` underflows subtracting it from `s.len()`.
+        let sample_size = self.display_rows.min(self.max_rows);
 
         let new_lazy_frame = self
             .original_lazy_frame
@@ -230,8 +432,93 @@ impl PeepFrame {
     pub fn reset_to_original(&mut self) -> Result<(), Box<dyn Error>> {
         self.update_with(&self.original_lazy_frame.clone(), ModifierState::Original)?;
 
+        self.active_query = None;
+
         Ok(())
     }
+
+    /// Toggles "follow" mode (`f`); the caller is responsible for starting
+    /// or stopping the filesystem watcher to match.
+    pub fn toggle_following(&mut self) {
+        self.following = !self.following;
+    }
+}
+
+/// Collapses a (possibly multi-line) SQL query down to a single line of
+/// whitespace-normalized text, for display in the header and for storing in
+/// the history ring buffer/dotfile.
+pub fn flatten_query(sql_query: &str) -> String {
+    sql_query.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Cap on the number of entries kept in the SQL history ring buffer.
+const MAX_HISTORY: usize = 200;
+
+fn history_path() -> Option<std::path::PathBuf> {
+    std::env::var_os("HOME").map(|home| std::path::PathBuf::from(home).join(".peepers_history"))
+}
+
+/// Loads previously executed queries from `~/.peepers_history`, one per line.
+/// Missing or unreadable history is treated as "no history yet".
+fn load_history() -> Vec<String> {
+    history_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|contents| {
+            let lines: Vec<&str> = contents.lines().collect();
+            let skip = lines.len().saturating_sub(MAX_HISTORY);
+            lines[skip..].iter().map(|line| line.to_string()).collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Appends `query` to `~/.peepers_history` so it survives restarts. Silently
+/// does nothing if `$HOME` isn't set or the file can't be written.
+fn append_history(query: &str) {
+    use std::io::Write;
+
+    let Some(path) = history_path() else {
+        return;
+    };
+
+    if let Ok(mut file) = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+    {
+        let _ = writeln!(file, "{query}");
+    }
+}
+
+/// Lazily scans `file_path` and reads its row count, using the Parquet
+/// footer metadata when available instead of collecting a count over the
+/// whole file.
+fn scan_file(
+    file_path: &str,
+    file_type: &file_utils::FileType,
+) -> Result<(LazyFrame, usize), Box<dyn Error>> {
+    let lf = match file_type {
+        file_utils::FileType::Parquet => {
+            LazyFrame::scan_parquet(file_path, ScanArgsParquet::default())?
+        }
+        file_utils::FileType::Csv => LazyCsvReader::new(file_path).finish()?,
+    };
+
+    let max_rows = match file_type {
+        file_utils::FileType::Parquet => {
+            ParquetReader::new(std::fs::File::open(file_path)?).num_rows()?
+        }
+        file_utils::FileType::Csv => lf
+            .clone()
+            .select([count().alias("count")])
+            .with_streaming(true)
+            .collect()?
+            .column("count")?
+            .u32()?
+            .get(0)
+            .ok_or("Unable to get row count")? as usize,
+    };
+
+    Ok((lf, max_rows))
 }
 
 fn sample_col(s: Series, num_sample: usize) -> Series {
@@ -1,41 +1,79 @@
-use crate::models::{ModifierState, PeepFrame};
+use crate::models::{flatten_query, AppMode, ModifierState, PeepFrame, SqlInputState};
 use polars::prelude::*;
-use std::error::Error;
-
-pub fn render(peep_frame: &PeepFrame) -> Result<(), Box<dyn Error>> {
-    let header = render_header(peep_frame);
-
-    let view = render_view_data(peep_frame);
-
-    let table = render_table(peep_frame)?;
-
-    let guide = render_controls_guide();
-
-    let output = format!("{}\n{}\n{}\n{}", header, view, table, guide);
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+use std::sync::OnceLock;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Style as SynStyle, ThemeSet};
+use syntect::parsing::SyntaxSet;
+use syntect::util::LinesWithEndings;
+
+static SYNTAX_SET: OnceLock<SyntaxSet> = OnceLock::new();
+static THEME_SET: OnceLock<ThemeSet> = OnceLock::new();
+
+pub fn render(frame: &mut Frame, peep_frame: &PeepFrame) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .split(area);
+
+    frame.render_widget(Paragraph::new(render_header(peep_frame)), chunks[0]);
+    frame.render_widget(Paragraph::new(render_view_data(peep_frame)), chunks[1]);
+
+    match render_table(peep_frame) {
+        Ok(table) => frame.render_widget(table, chunks[2]),
+        Err(err) => frame.render_widget(
+            Paragraph::new(format!("Unable to render table: {err}")),
+            chunks[2],
+        ),
+    }
 
-    println!("{}", output);
+    frame.render_widget(Paragraph::new(render_controls_guide()), chunks[3]);
 
-    Ok(())
+    if let AppMode::SqlInput(state) = &peep_frame.mode {
+        render_sql_modal(frame, area, state);
+    }
 }
 
 fn render_header(peep_frame: &PeepFrame) -> String {
-    match peep_frame.modifier_state {
+    let label = match peep_frame.modifier_state {
         ModifierState::Original => {
             format!("Table name: '{}'", peep_frame.file_name)
         }
         ModifierState::Queried => {
-            format!("SQL result: '{}'", peep_frame.file_name)
+            let query = flatten_query(peep_frame.active_query.as_deref().unwrap_or_default());
+            format!("SQL result: '{}' | {}", peep_frame.file_name, query)
         }
         ModifierState::RandomRows => {
             format!("Sampling rows: '{}'", peep_frame.file_name)
         }
+    };
+
+    if peep_frame.following {
+        format!("{label} | [following]")
+    } else {
+        label
     }
 }
 
-fn render_table(peep_frame: &PeepFrame) -> Result<String, PolarsError> {
+fn render_table(peep_frame: &PeepFrame) -> Result<Table<'static>, PolarsError> {
     let (start, end) = peep_frame.col_slice_state;
     let selected_columns: Vec<String> = peep_frame.col_names[start..end].into_vec();
 
+    // enable slice pushdown so only the row group(s) covering this window
+    // are decoded, instead of the whole file
     let df = peep_frame
         .current_lazy_frame
         .clone()
@@ -45,9 +83,31 @@ fn render_table(peep_frame: &PeepFrame) -> Result<String, PolarsError> {
         )
         .select([cols(selected_columns)])
         .with_streaming(true)
+        .with_slice_pushdown(true)
         .collect()?;
 
-    Ok(df.to_string())
+    let header = Row::new(
+        df.get_column_names()
+            .iter()
+            .map(|name| Cell::from(name.to_string()))
+            .collect::<Vec<_>>(),
+    )
+    .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let mut rows = Vec::with_capacity(df.height());
+    for idx in 0..df.height() {
+        let mut cells = Vec::with_capacity(df.width());
+        for series in df.get_columns() {
+            cells.push(Cell::from(series.get(idx)?.to_string()));
+        }
+        rows.push(Row::new(cells));
+    }
+
+    let widths = vec![Constraint::Min(10); df.width()];
+
+    Ok(Table::new(rows, widths)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL)))
 }
 
 fn render_view_data(peep_frame: &PeepFrame) -> String {
@@ -86,5 +146,92 @@ fn render_view_data(peep_frame: &PeepFrame) -> String {
 }
 
 fn render_controls_guide() -> String {
-    "Arrow keys | [s]ql | [r]andom | [t]ail | [h]ead | [o]riginal | [q]uit".to_string()
+    "Arrow keys | [s]ql | [r]andom | [t]ail | [h]ead | [o]riginal | [f]ollow | [q]uit".to_string()
+}
+
+fn render_sql_modal(frame: &mut Frame, area: Rect, state: &SqlInputState) {
+    let modal_area = centered_rect(60, 40, area);
+
+    frame.render_widget(Clear, modal_area);
+
+    let mut lines = highlight_sql(&state.buffer);
+    if lines.is_empty() {
+        lines.push(Line::default());
+    }
+    if let Some(error) = &state.error {
+        lines.push(Line::from(Span::styled(
+            error.as_str(),
+            Style::default().fg(Color::Red),
+        )));
+    }
+
+    let block = Block::default()
+        .title("SQL (Ctrl+X to run, Enter for newline, Up/Down for history, Esc to cancel)")
+        .borders(Borders::ALL);
+
+    frame.render_widget(Paragraph::new(lines).block(block), modal_area);
+
+    let (line, col) = state.cursor_position();
+    frame.set_cursor_position((
+        modal_area.x + 1 + col as u16,
+        modal_area.y + 1 + line as u16,
+    ));
+}
+
+/// Tokenizes `buffer` as SQL with `syntect`, producing one styled `Line` per
+/// source line for the modal's `Paragraph`.
+fn highlight_sql(buffer: &str) -> Vec<Line<'static>> {
+    let syntax_set = SYNTAX_SET.get_or_init(SyntaxSet::load_defaults_newlines);
+    let theme_set = THEME_SET.get_or_init(ThemeSet::load_defaults);
+
+    let syntax = syntax_set
+        .find_syntax_by_token("sql")
+        .unwrap_or_else(|| syntax_set.find_syntax_plain_text());
+    let theme = &theme_set.themes["base16-ocean.dark"];
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    LinesWithEndings::from(buffer)
+        .map(|line| {
+            let ranges = highlighter
+                .highlight_line(line, syntax_set)
+                .unwrap_or_default();
+
+            let spans: Vec<Span<'static>> = ranges
+                .into_iter()
+                .map(|(style, text)| {
+                    Span::styled(text.trim_end_matches('\n').to_string(), synstyle_to_ratatui(style))
+                })
+                .collect();
+
+            Line::from(spans)
+        })
+        .collect()
+}
+
+fn synstyle_to_ratatui(style: SynStyle) -> Style {
+    Style::default().fg(Color::Rgb(
+        style.foreground.r,
+        style.foreground.g,
+        style.foreground.b,
+    ))
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
 }